@@ -1,13 +1,135 @@
 use eframe::egui;
 use rusqlite::{params, Connection, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TodoItem {
     id: i32,
     title: String,
     description: Option<String>,
     done: bool,
     deleted: bool,
+    due_date: Option<String>,
+    tags: Option<String>,
+}
+
+/// Split a stored comma-separated `tags` string into trimmed, non-empty chips.
+fn parse_tags(tags: &Option<String>) -> Vec<String> {
+    tags.as_deref()
+        .map(|s| {
+            s.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Normalize a comma-separated tags input into storage form, or `None` if empty.
+fn normalize_tags(raw: &str) -> Option<String> {
+    let tags = parse_tags(&Some(raw.to_string()));
+    if tags.is_empty() {
+        None
+    } else {
+        Some(tags.join(", "))
+    }
+}
+
+/// Return `true` if `s` is a valid `YYYY-MM-DD` calendar date.
+fn is_valid_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return false;
+    }
+    if !parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit())) {
+        return false;
+    }
+    let (y, m, d) = match (
+        parts[0].parse::<i32>(),
+        parts[1].parse::<u32>(),
+        parts[2].parse::<u32>(),
+    ) {
+        (Ok(y), Ok(m), Ok(d)) => (y, m, d),
+        _ => return false,
+    };
+    if m < 1 || m > 12 || d < 1 {
+        return false;
+    }
+    d <= days_in_month(y, m)
+}
+
+fn days_in_month(y: i32, m: u32) -> u32 {
+    match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (y % 4 == 0 && y % 100 != 0) || y % 400 == 0 => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Convert a day count since the Unix epoch into a `(year, month, day)` triple.
+/// Standard "civil from days" algorithm (Howard Hinnant), avoids a date crate dependency.
+fn civil_from_days(z: i64) -> (i32, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = if m <= 2 { y + 1 } else { y };
+    (y as i32, m, d)
+}
+
+/// Today's date as `YYYY-MM-DD`, used to highlight overdue todos.
+fn today_string() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (y, m, d) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+type Migration = fn(&Connection) -> Result<()>;
+
+/// Ordered schema migrations, applied once each in sequence. Add new entries to
+/// the end as the schema evolves; never edit or reorder existing ones, since
+/// `user_version` records how many have already run against a given database.
+const MIGRATIONS: &[Migration] = &[
+    |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS todos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                description TEXT,
+                done BOOLEAN NOT NULL,
+                deleted BOOLEAN NOT NULL DEFAULT 0
+            )",
+        )
+    },
+    |conn| conn.execute_batch("ALTER TABLE todos ADD COLUMN due_date TEXT"),
+    |conn| conn.execute_batch("ALTER TABLE todos ADD COLUMN tags TEXT"),
+];
+
+/// Bring `conn`'s schema up to the latest version, tracked via SQLite's
+/// built-in `PRAGMA user_version`. Only migrations beyond the current version
+/// run, inside a single transaction, so upgrading an existing `todos.db`
+/// never loses data or hits "no such column" errors.
+fn run_migrations(conn: &mut Connection) -> Result<()> {
+    let current_version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let tx = conn.transaction()?;
+    for (i, migration) in MIGRATIONS.iter().enumerate() {
+        let version = i as i32 + 1;
+        if version > current_version {
+            migration(&tx)?;
+        }
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len() as i32)?;
+    tx.commit()
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -23,134 +145,146 @@ struct TodoApp {
     todos: Vec<TodoItem>,
     new_title: String,
     new_description: String,
+    new_due_date: String,
+    new_tags: String,
     edit_todo_id: Option<i32>,
     edit_title: String,
     edit_description: String,
+    edit_due_date: String,
+    edit_tags: String,
     filter: Filter,
+    search_query: String,
+    last_error: Option<String>,
 }
 
 impl TodoApp {
     /// Initialize the app: open DB, create table, and load todos.
     fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        let conn = Connection::open("todos.db").expect("Failed to open DB");
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS todos (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                description TEXT,
-                done BOOLEAN NOT NULL,
-                deleted BOOLEAN NOT NULL DEFAULT 0
-            )",
-            [],
-        )
-        .expect("Failed to create table");
+        let mut conn = Connection::open("todos.db").expect("Failed to open DB");
+        run_migrations(&mut conn).expect("Failed to run migrations");
 
         let mut app = Self {
             conn,
             todos: Vec::new(),
             new_title: String::new(),
             new_description: String::new(),
+            new_due_date: String::new(),
+            new_tags: String::new(),
             edit_todo_id: None,
             edit_title: String::new(),
             edit_description: String::new(),
+            edit_due_date: String::new(),
+            edit_tags: String::new(),
             filter: Filter::All,
+            search_query: String::new(),
+            last_error: None,
         };
-        app.load_todos();
+        if let Err(e) = app.load_todos() {
+            app.last_error = Some(e.to_string());
+        }
         app
     }
 
     /// Load all todos from SQLite.
-    fn load_todos(&mut self) {
+    fn load_todos(&mut self) -> Result<()> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, title, description, done, deleted FROM todos")
-            .expect("Failed to prepare query");
-        let rows = stmt
-            .query_map([], |row| {
-                Ok(TodoItem {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    description: row.get(2)?,
-                    done: row.get(3)?,
-                    deleted: row.get(4)?,
-                })
+            .prepare("SELECT id, title, description, done, deleted, due_date, tags FROM todos")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TodoItem {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get(2)?,
+                done: row.get(3)?,
+                deleted: row.get(4)?,
+                due_date: row.get(5)?,
+                tags: row.get(6)?,
             })
-            .expect("Failed to query todos");
+        })?;
 
-        self.todos = rows.map(|r| r.unwrap()).collect();
+        self.todos = rows.collect::<Result<Vec<_>>>()?;
+        Ok(())
     }
 
     /// Insert a new todo.
-    fn add_todo(&mut self) {
+    fn add_todo(&mut self) -> Result<()> {
         let title = self.new_title.trim();
-        if !title.is_empty() {
+        let due = self.new_due_date.trim();
+        if !title.is_empty() && (due.is_empty() || is_valid_date(due)) {
             let desc = self.new_description.trim();
             let desc_opt = if desc.is_empty() { None } else { Some(desc) };
-            self.conn
-                .execute(
-                    "INSERT INTO todos (title, description, done, deleted) VALUES (?1, ?2, 0, 0)",
-                    params![title, desc_opt],
-                )
-                .expect("Failed to insert todo");
+            let due_opt = if due.is_empty() { None } else { Some(due) };
+            let tags_opt = normalize_tags(&self.new_tags);
+            self.conn.execute(
+                "INSERT INTO todos (title, description, done, deleted, due_date, tags) VALUES (?1, ?2, 0, 0, ?3, ?4)",
+                params![title, desc_opt, due_opt, tags_opt],
+            )?;
             self.new_title.clear();
             self.new_description.clear();
-            self.load_todos();
+            self.new_due_date.clear();
+            self.new_tags.clear();
+            self.load_todos()?;
         }
+        Ok(())
     }
 
     /// Toggle completion state.
-    fn toggle_done(&mut self, id: i32, current: bool) {
-        self.conn
-            .execute(
-                "UPDATE todos SET done = ?1 WHERE id = ?2",
-                params![!current, id],
-            )
-            .expect("Failed to update todo");
-        self.load_todos();
+    fn toggle_done(&mut self, id: i32, current: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE todos SET done = ?1 WHERE id = ?2",
+            params![!current, id],
+        )?;
+        self.load_todos()
     }
 
     /// Mark a todo as deleted (soft delete).
-    fn delete_todo(&mut self, id: i32) {
+    fn delete_todo(&mut self, id: i32) -> Result<()> {
         self.conn
-            .execute(
-                "UPDATE todos SET deleted = 1 WHERE id = ?1",
-                params![id],
-            )
-            .expect("Failed to mark deleted");
-        self.load_todos();
+            .execute("UPDATE todos SET deleted = 1 WHERE id = ?1", params![id])?;
+        self.load_todos()
     }
 
     /// Restore a soft-deleted todo.
-    fn restore_todo(&mut self, id: i32) {
+    fn restore_todo(&mut self, id: i32) -> Result<()> {
         self.conn
-            .execute(
-                "UPDATE todos SET deleted = 0 WHERE id = ?1",
-                params![id],
-            )
-            .expect("Failed to restore todo");
-        self.load_todos();
+            .execute("UPDATE todos SET deleted = 0 WHERE id = ?1", params![id])?;
+        self.load_todos()
     }
 
     /// Update the title and description of an existing todo using internal fields.
-    fn update_todo(&mut self, id: i32) {
+    /// The due date and tags are saved along with them.
+    /// Returns `Ok(true)` on a successful save, `Ok(false)` if the edit was rejected
+    /// as invalid (with the reason recorded in `last_error`), so the caller knows
+    /// whether to close the edit form or let the user fix their input.
+    fn update_todo(&mut self, id: i32) -> Result<bool> {
         let t = self.edit_title.trim();
         if t.is_empty() {
-            return;
+            self.last_error = Some("Title cannot be empty".to_string());
+            return Ok(false);
+        }
+        let due = self.edit_due_date.trim();
+        if !due.is_empty() && !is_valid_date(due) {
+            self.last_error = Some(format!("Invalid due date \"{due}\" (expected YYYY-MM-DD)"));
+            return Ok(false);
         }
         let d = self.edit_description.trim();
         let d_opt = if d.is_empty() { None } else { Some(d) };
-        self.conn
-            .execute(
-                "UPDATE todos SET title = ?1, description = ?2 WHERE id = ?3",
-                params![t, d_opt, id],
-            )
-            .expect("Failed to update todo");
-        self.load_todos();
+        let due_opt = if due.is_empty() { None } else { Some(due) };
+        let tags_opt = normalize_tags(&self.edit_tags);
+        self.conn.execute(
+            "UPDATE todos SET title = ?1, description = ?2, due_date = ?3, tags = ?4 WHERE id = ?5",
+            params![t, d_opt, due_opt, tags_opt, id],
+        )?;
+        self.load_todos()?;
+        Ok(true)
     }
 
-    /// Return todos filtered by the current `filter` setting.
+    /// Return todos filtered by the current `filter` setting and search query,
+    /// ordered by due date (items with no due date sort last).
     fn filtered_todos(&self) -> Vec<TodoItem> {
-        self.todos
+        let query = self.search_query.trim().to_lowercase();
+        let mut todos: Vec<TodoItem> = self
+            .todos
             .iter()
             .cloned()
             .filter(|t| match self.filter {
@@ -159,7 +293,81 @@ impl TodoApp {
                 Filter::Completed => t.done && !t.deleted,
                 Filter::Deleted => t.deleted,
             })
-            .collect()
+            .filter(|t| {
+                query.is_empty()
+                    || t.title.to_lowercase().contains(&query)
+                    || t.description
+                        .as_deref()
+                        .is_some_and(|d| d.to_lowercase().contains(&query))
+                    || parse_tags(&t.tags)
+                        .iter()
+                        .any(|tag| tag.to_lowercase().contains(&query))
+            })
+            .collect();
+        todos.sort_by(|a, b| match (&a.due_date, &b.due_date) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        todos
+    }
+
+    /// Export all todos (including done/deleted flags) to a pretty-printed JSON array.
+    fn export_json(&self, path: &str) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(&self.todos)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Import todos from a JSON array previously written by `export_json`, upserting
+    /// by `id` so re-importing the same file preserves completion state.
+    fn import_json(&mut self, path: &str) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let data = std::fs::read_to_string(path)?;
+        let items: Vec<TodoItem> = serde_json::from_str(&data)?;
+
+        let tx = self.conn.transaction()?;
+        for item in &items {
+            tx.execute(
+                "INSERT INTO todos (id, title, description, done, deleted, due_date, tags)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(id) DO UPDATE SET
+                    title = excluded.title,
+                    description = excluded.description,
+                    done = excluded.done,
+                    deleted = excluded.deleted,
+                    due_date = excluded.due_date,
+                    tags = excluded.tags",
+                params![
+                    item.id,
+                    item.title,
+                    item.description,
+                    item.done,
+                    item.deleted,
+                    item.due_date,
+                    item.tags
+                ],
+            )?;
+        }
+        tx.commit()?;
+        self.load_todos()?;
+        Ok(())
+    }
+
+    /// Soft-delete every completed, non-deleted todo in one transaction.
+    fn clear_completed(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("UPDATE todos SET deleted = 1 WHERE done = 1 AND deleted = 0", [])?;
+        tx.commit()?;
+        self.load_todos()
+    }
+
+    /// Permanently remove every soft-deleted todo, reclaiming the space they used.
+    fn empty_trash(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM todos WHERE deleted = 1", [])?;
+        tx.commit()?;
+        self.load_todos()
     }
 }
 
@@ -168,6 +376,22 @@ impl eframe::App for TodoApp {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("ðŸš€ TODO List");
 
+            // Dismissible error banner for failed DB operations.
+            if let Some(err) = self.last_error.clone() {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(150, 30, 30))
+                    .inner_margin(6.0)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(egui::Color32::WHITE, format!("âš  {err}"));
+                            if ui.small_button("Ã—").clicked() {
+                                self.last_error = None;
+                            }
+                        });
+                    });
+                ui.separator();
+            }
+
             // Add new todo: title + optional description
             ui.horizontal(|ui| {
                 ui.add(
@@ -179,13 +403,58 @@ impl eframe::App for TodoApp {
                         .desired_rows(1)
                         .hint_text("Description (optional)"),
                 );
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_due_date)
+                        .hint_text("Due date (YYYY-MM-DD)")
+                        .desired_width(120.0),
+                );
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_tags)
+                        .hint_text("Tags (comma separated)"),
+                );
                 if ui.button("Add").clicked() {
-                    self.add_todo();
+                    if let Err(e) = self.add_todo() {
+                        self.last_error = Some(e.to_string());
+                    }
                 }
             });
 
             ui.separator();
 
+            // Export/import the whole list as JSON, plus bulk clear/empty-trash actions.
+            ui.horizontal(|ui| {
+                if ui.button("Export").clicked() {
+                    if let Err(e) = self.export_json("todos_export.json") {
+                        self.last_error = Some(e.to_string());
+                    }
+                }
+                if ui.button("Import").clicked() {
+                    if let Err(e) = self.import_json("todos_export.json") {
+                        self.last_error = Some(e.to_string());
+                    }
+                }
+                if ui.button("Clear completed").clicked() {
+                    if let Err(e) = self.clear_completed() {
+                        self.last_error = Some(e.to_string());
+                    }
+                }
+                if self.filter == Filter::Deleted && ui.button("Empty trash").clicked() {
+                    if let Err(e) = self.empty_trash() {
+                        self.last_error = Some(e.to_string());
+                    }
+                }
+            });
+
+            ui.separator();
+
+            // Free-text search over title, description, and tags.
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search_query)
+                    .hint_text("Search title, description, or tags..."),
+            );
+
+            ui.separator();
+
             // Filter selector
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.filter, Filter::All, "All");
@@ -204,7 +473,9 @@ impl eframe::App for TodoApp {
                         if !todo.deleted {
                             let mut done = todo.done;
                             if ui.checkbox(&mut done, "").clicked() {
-                                self.toggle_done(todo.id, todo.done);
+                                if let Err(e) = self.toggle_done(todo.id, todo.done) {
+                                    self.last_error = Some(e.to_string());
+                                }
                             }
                         } else {
                             ui.add_enabled(false, egui::Checkbox::new(&mut false, ""));
@@ -212,10 +483,31 @@ impl eframe::App for TodoApp {
 
                         // Title and description display
                         ui.vertical(|ui| {
-                            ui.label(&todo.title);
+                            let overdue = !todo.done
+                                && todo
+                                    .due_date
+                                    .as_deref()
+                                    .is_some_and(|d| d < today_string().as_str());
+                            if overdue {
+                                ui.colored_label(egui::Color32::RED, &todo.title);
+                            } else {
+                                ui.label(&todo.title);
+                            }
                             if let Some(desc) = &todo.description {
                                 ui.label(desc);
                             }
+                            if let Some(due) = &todo.due_date {
+                                ui.small(format!("Due: {due}"));
+                            }
+                            if !parse_tags(&todo.tags).is_empty() {
+                                ui.horizontal(|ui| {
+                                    for tag in parse_tags(&todo.tags) {
+                                        if ui.small_button(format!("#{tag}")).clicked() {
+                                            self.search_query = tag;
+                                        }
+                                    }
+                                });
+                            }
                         });
 
                         // Actions: edit/delete or restore
@@ -228,9 +520,21 @@ impl eframe::App for TodoApp {
                                 egui::TextEdit::multiline(&mut self.edit_description)
                                     .desired_rows(1),
                             );
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.edit_due_date)
+                                    .hint_text("Due date (YYYY-MM-DD)")
+                                    .desired_width(120.0),
+                            );
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.edit_tags)
+                                    .hint_text("Tags (comma separated)"),
+                            );
                             if ui.button("Save").clicked() {
-                                self.update_todo(todo.id);
-                                self.edit_todo_id = None;
+                                match self.update_todo(todo.id) {
+                                    Ok(true) => self.edit_todo_id = None,
+                                    Ok(false) => {}
+                                    Err(e) => self.last_error = Some(e.to_string()),
+                                }
                             }
                             if ui.button("Cancel").clicked() {
                                 self.edit_todo_id = None;
@@ -240,13 +544,19 @@ impl eframe::App for TodoApp {
                                 self.edit_todo_id = Some(todo.id);
                                 self.edit_title = todo.title.clone();
                                 self.edit_description = todo.description.clone().unwrap_or_default();
+                                self.edit_due_date = todo.due_date.clone().unwrap_or_default();
+                                self.edit_tags = todo.tags.clone().unwrap_or_default();
                             }
                             if ui.small_button("ðŸ—‘ï¸").clicked() {
-                                self.delete_todo(todo.id);
+                                if let Err(e) = self.delete_todo(todo.id) {
+                                    self.last_error = Some(e.to_string());
+                                }
                             }
                         } else {
                             if ui.small_button("â†©ï¸").clicked() {
-                                self.restore_todo(todo.id);
+                                if let Err(e) = self.restore_todo(todo.id) {
+                                    self.last_error = Some(e.to_string());
+                                }
                             }
                         }
                     });